@@ -1,67 +1,150 @@
 #[cfg(any(target_os = "macos", target_os = "linux"))]
 use bevy::window::CompositeAlphaMode;
-use bevy::window::{WindowFocused, WindowMoved, WindowResized};
-use bevy::{prelude::*, render::render_resource::Extent3d, window::WindowResolution};
+use bevy::window::WindowFocused;
+use bevy::{
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+        texture::ImageSampler,
+    },
+    window::WindowResolution,
+};
 use crossbeam_channel::{Receiver, Sender, bounded};
+use image::{ImageBuffer, Rgba};
+use std::io::Write as _;
+use std::path::PathBuf;
 use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+use terminal_size::{Height, Width, terminal_size};
 use xcap::Monitor;
 
+mod recorder;
+
 #[derive(Component)]
 struct Screencap;
 
 #[derive(Resource)]
 struct FrameReceiver(Receiver<Vec<u8>>);
 
-#[derive(Resource)]
-struct WindowSize {
-    width: f32,
-    height: f32,
+/// The rectangle actually captured from the monitor. Independent of the
+/// floating widget window, so the source can be aimed anywhere on screen
+/// while the window just displays whatever comes back. `zoom_factor` only
+/// affects how large the crop is displayed, not what's captured.
+/// `filter_nearest` picks the sampler used to stretch it to that size.
+#[derive(Resource, Clone, Copy)]
+struct CaptureRegion {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    zoom_factor: f32,
+    filter_nearest: bool,
 }
 
 #[derive(Resource)]
-struct ResizeSender(Sender<(u32, u32)>);
+struct RegionSender(Sender<(usize, u32, u32, u32, u32)>);
 
-#[derive(Resource)]
-struct WindowPosition {
-    x: u32,
-    y: u32,
+/// A monitor's resolution and its offset in the virtual desktop, the latter
+/// only used for display/logging since each monitor's captured frame is
+/// already local to its own `(0, 0)` origin.
+#[derive(Clone, Copy)]
+struct MonitorBounds {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
 }
 
 #[derive(Resource)]
-struct PositionSender(Sender<(u32, u32)>);
+struct MonitorList(Vec<MonitorBounds>);
+
+/// Index into `MonitorList` of the monitor currently being captured.
+#[derive(Resource, Clone, Copy)]
+struct ActiveMonitor(usize);
+
+/// Most recently displayed crop, kept around so the save hotkey doesn't have
+/// to race `update_sprite_image` for the next value off `FrameReceiver`.
+/// Dimensions are stored together with the bytes, so a resize after a frame
+/// arrives can't pair that frame with the new `CaptureRegion` size, and a
+/// frame whose length doesn't match the region's current size (cropped
+/// before a resize reached the capture thread) isn't kept at all.
+#[derive(Resource, Default)]
+struct LastFrame(Option<(u32, u32, Vec<u8>)>);
+
+/// Shrinks/moves `region` so it fits entirely within `monitor`, so
+/// `crop_frame`'s out-of-bounds guard never silently hands back a black
+/// frame after switching to a smaller monitor.
+fn clamp_region_to_monitor(region: &mut CaptureRegion, monitor: &MonitorBounds) {
+    region.width = region.width.min(monitor.width.max(1));
+    region.height = region.height.min(monitor.height.max(1));
+    region.x = region.x.min(monitor.width.saturating_sub(region.width));
+    region.y = region.y.min(monitor.height.saturating_sub(region.height));
+}
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let terminal_mode = args.iter().any(|a| a == "--terminal");
+    let lores = args.iter().any(|a| a == "--lores");
+    let record_mode = args.iter().any(|a| a == "--record");
+
     let (tx, rx) = bounded(1);
-    let (resize_tx, resize_rx) = bounded(1);
-    let (position_tx, position_rx) = bounded(1);
-    let frame_receiver = FrameReceiver(rx);
-    let window_size = WindowSize {
-        width: 800.0,
-        height: 600.0,
-    }; // Default size
-    let resize_sender = ResizeSender(resize_tx);
-    let window_position = WindowPosition { x: 0, y: 0 }; // Default position
-    let position_sender = PositionSender(position_tx);
+    let (region_tx, region_rx) = crossbeam_channel::unbounded();
+
+    let monitors = Monitor::all().expect("failed to enumerate monitors");
+    assert!(!monitors.is_empty(), "no monitors found");
+    let monitor_list = MonitorList(
+        monitors
+            .iter()
+            .map(|m| MonitorBounds {
+                x: m.x().unwrap_or(0),
+                y: m.y().unwrap_or(0),
+                width: m.width().unwrap_or(0),
+                height: m.height().unwrap_or(0),
+            })
+            .collect(),
+    );
+
+    let mut capture_region = CaptureRegion {
+        x: 1,
+        y: 1,
+        width: 64,
+        height: 64,
+        zoom_factor: 1.0,
+        filter_nearest: false,
+    }; // Default region
+    clamp_region_to_monitor(&mut capture_region, &monitor_list.0[0]);
+    let region_sender = RegionSender(region_tx);
 
     thread::spawn(move || {
-        let monitor = Monitor::from_point(0, 0).unwrap();
-        let (video_recorder, sx) = monitor.video_recorder().unwrap();
+        let mut monitor_index = 0usize;
+        let (mut video_recorder, mut sx) = monitors[monitor_index].video_recorder().unwrap();
         video_recorder.start().unwrap();
 
-        let mut current_width = 64;
-        let mut current_height = 64;
-        let mut current_x = 1;
-        let mut current_y = 1;
+        let mut current_x = capture_region.x;
+        let mut current_y = capture_region.y;
+        let mut current_width = capture_region.width;
+        let mut current_height = capture_region.height;
 
         loop {
-            if let Ok((new_width, new_height)) = resize_rx.try_recv() {
+            if let Ok((new_monitor_index, new_x, new_y, new_width, new_height)) =
+                region_rx.try_recv()
+            {
+                current_x = new_x;
+                current_y = new_y;
                 current_width = new_width;
                 current_height = new_height;
-            }
 
-            if let Ok((new_x, new_y)) = position_rx.try_recv() {
-                current_x = new_x;
-                current_y = new_y;
+                if new_monitor_index != monitor_index && new_monitor_index < monitors.len() {
+                    let _ = video_recorder.stop();
+                    monitor_index = new_monitor_index;
+                    let (new_recorder, new_sx) =
+                        monitors[monitor_index].video_recorder().unwrap();
+                    new_recorder.start().unwrap();
+                    video_recorder = new_recorder;
+                    sx = new_sx;
+                }
             }
 
             match sx.recv() {
@@ -83,34 +166,81 @@ fn main() {
         }
     });
 
-    App::new()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                // Setting `transparent` allows the `ClearColor`'s alpha value to take effect
-                transparent: true,
-                // Disabling window decorations to make it feel more like a widget than a window
-                decorations: true,
-                resolution: WindowResolution::new(800.0, 600.0).with_scale_factor_override(1.0),
-                #[cfg(target_os = "macos")]
-                composite_alpha_mode: CompositeAlphaMode::PostMultiplied,
-                #[cfg(target_os = "linux")]
-                composite_alpha_mode: CompositeAlphaMode::PreMultiplied,
-                ..default()
-            }),
+    if terminal_mode {
+        run_terminal_preview(rx, capture_region, lores);
+        return;
+    }
+
+    let frame_receiver = FrameReceiver(rx);
+
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(Window {
+            // Setting `transparent` allows the `ClearColor`'s alpha value to take effect
+            transparent: true,
+            // Disabling window decorations to make it feel more like a widget than a window
+            decorations: true,
+            resolution: WindowResolution::new(800.0, 600.0).with_scale_factor_override(1.0),
+            #[cfg(target_os = "macos")]
+            composite_alpha_mode: CompositeAlphaMode::PostMultiplied,
+            #[cfg(target_os = "linux")]
+            composite_alpha_mode: CompositeAlphaMode::PreMultiplied,
+            ..default()
+        }),
+        ..default()
+    }));
+
+    if record_mode {
+        let mut images = app.world_mut().resource_mut::<Assets<Image>>();
+        let size = Extent3d {
+            width: capture_region.width,
+            height: capture_region.height,
             ..default()
-        }))
+        };
+        let mut target_image = Image::new_fill(
+            size,
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        target_image.texture_descriptor.usage |=
+            TextureUsages::COPY_SRC | TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING;
+        let target_handle = images.add(target_image);
+
+        app.world_mut().spawn((
+            Camera2d,
+            Camera {
+                target: RenderTarget::Image(target_handle.clone()),
+                ..default()
+            },
+        ));
+
+        app.insert_resource(recorder::RecordTarget {
+            image: target_handle,
+            width: size.width,
+            height: size.height,
+            out_dir: PathBuf::from("recording"),
+        });
+        app.add_plugins(recorder::RecorderPlugin);
+    }
+
+    app
         // ClearColor must have 0 alpha, otherwise some color will bleed through
         .insert_resource(ClearColor(Color::NONE))
         .insert_resource(frame_receiver)
-        .insert_resource(window_size)
-        .insert_resource(resize_sender)
-        .insert_resource(window_position)
-        .insert_resource(position_sender)
+        .insert_resource(capture_region)
+        .insert_resource(region_sender)
+        .insert_resource(monitor_list)
+        .insert_resource(ActiveMonitor(0))
+        .insert_resource(LastFrame::default())
         .add_systems(Startup, setup)
         .add_systems(Update, update_sprite_image)
-        .add_systems(Update, on_resize_system)
-        .add_systems(Update, on_move_system)
+        .add_systems(Update, on_region_control_system)
+        .add_systems(Update, on_monitor_control_system)
+        .add_systems(Update, on_region_update_system)
         .add_systems(Update, on_focus_system)
+        .add_systems(Update, save_frame_on_keypress)
         .run();
 }
 
@@ -125,23 +255,139 @@ fn setup(mut commands: Commands) {
     ));
 }
 
-fn on_resize_system(
+const REGION_NUDGE_STEP: u32 = 10;
+const REGION_RESIZE_STEP: u32 = 10;
+const ZOOM_STEP: f32 = 0.1;
+const MIN_ZOOM: f32 = 0.1;
+
+/// Arrow keys nudge the captured rectangle's origin; holding Shift switches
+/// them to resizing the rectangle instead, so the region can be aimed and
+/// sized independently of the widget window. `+`/`-` adjust how large that
+/// region is displayed without changing what's captured, and `F` toggles
+/// between bilinear and nearest-neighbor filtering for that stretch.
+fn on_region_control_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut region: ResMut<CaptureRegion>,
+    monitors: Res<MonitorList>,
+    active_monitor: Res<ActiveMonitor>,
+    region_sender: Res<RegionSender>,
+) {
+    let resizing =
+        keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
+    let mut dims_changed = false;
+
+    if resizing {
+        if keyboard.just_pressed(KeyCode::ArrowRight) {
+            region.width += REGION_RESIZE_STEP;
+            dims_changed = true;
+        }
+        if keyboard.just_pressed(KeyCode::ArrowLeft) {
+            region.width = region.width.saturating_sub(REGION_RESIZE_STEP).max(1);
+            dims_changed = true;
+        }
+        if keyboard.just_pressed(KeyCode::ArrowDown) {
+            region.height += REGION_RESIZE_STEP;
+            dims_changed = true;
+        }
+        if keyboard.just_pressed(KeyCode::ArrowUp) {
+            region.height = region.height.saturating_sub(REGION_RESIZE_STEP).max(1);
+            dims_changed = true;
+        }
+    } else {
+        if keyboard.just_pressed(KeyCode::ArrowRight) {
+            region.x += REGION_NUDGE_STEP;
+            dims_changed = true;
+        }
+        if keyboard.just_pressed(KeyCode::ArrowLeft) {
+            region.x = region.x.saturating_sub(REGION_NUDGE_STEP);
+            dims_changed = true;
+        }
+        if keyboard.just_pressed(KeyCode::ArrowDown) {
+            region.y += REGION_NUDGE_STEP;
+            dims_changed = true;
+        }
+        if keyboard.just_pressed(KeyCode::ArrowUp) {
+            region.y = region.y.saturating_sub(REGION_NUDGE_STEP);
+            dims_changed = true;
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::Equal) {
+        region.zoom_factor += ZOOM_STEP;
+    }
+    if keyboard.just_pressed(KeyCode::Minus) {
+        region.zoom_factor = (region.zoom_factor - ZOOM_STEP).max(MIN_ZOOM);
+    }
+    if keyboard.just_pressed(KeyCode::KeyF) {
+        region.filter_nearest = !region.filter_nearest;
+    }
+
+    if dims_changed {
+        if let Some(bounds) = monitors.0.get(active_monitor.0) {
+            clamp_region_to_monitor(&mut region, bounds);
+        }
+        // The region channel is unbounded, so this never blocks the main
+        // schedule and never drops an update; the capture thread applies
+        // queued updates in order, one per captured frame.
+        let _ = region_sender.0.try_send((
+            active_monitor.0,
+            region.x,
+            region.y,
+            region.width,
+            region.height,
+        ));
+    }
+}
+
+/// Cycles through `MonitorList` on `M`, clamping the region to the newly
+/// active monitor's bounds and telling the capture thread to tear down its
+/// `video_recorder` and restart against the new monitor.
+fn on_monitor_control_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    monitors: Res<MonitorList>,
+    mut active_monitor: ResMut<ActiveMonitor>,
+    mut region: ResMut<CaptureRegion>,
+    region_sender: Res<RegionSender>,
+) {
+    if !keyboard.just_pressed(KeyCode::KeyM) || monitors.0.is_empty() {
+        return;
+    }
+
+    active_monitor.0 = (active_monitor.0 + 1) % monitors.0.len();
+    let bounds = monitors.0[active_monitor.0];
+    clamp_region_to_monitor(&mut region, &bounds);
+
+    // Same as `on_region_control_system`: the unbounded channel never blocks
+    // the main schedule and never drops the monitor switch.
+    let _ = region_sender.0.try_send((
+        active_monitor.0,
+        region.x,
+        region.y,
+        region.width,
+        region.height,
+    ));
+
+    println!(
+        "Switched to monitor {} ({}x{} at {},{})",
+        active_monitor.0, bounds.width, bounds.height, bounds.x, bounds.y
+    );
+}
+
+/// Keeps the sprite's backing image sized to the captured region's native
+/// resolution so incoming crops always fit the buffer they're copied into,
+/// then lets the GPU stretch the sprite to `zoom_factor` instead of
+/// resampling the raw bytes on the CPU, using whichever sampler
+/// `CaptureRegion::filter_nearest` currently selects.
+fn on_region_update_system(
     mut images: ResMut<Assets<Image>>,
-    mut window_size: ResMut<WindowSize>,
-    mut resize_reader: EventReader<WindowResized>,
-    query: Query<(&Screencap, &mut Sprite)>,
-    resize_sender: Res<ResizeSender>,
+    region: Res<CaptureRegion>,
+    mut query: Query<(&Screencap, &mut Sprite)>,
 ) {
-    for e in resize_reader.read() {
-        window_size.width = e.width;
-        window_size.height = e.height;
-        resize_sender
-            .0
-            .send((e.width as u32, e.height as u32))
-            .unwrap();
+    if !region.is_changed() {
+        return;
     }
 
-    let Ok((_, sprite)) = query.get_single() else {
+    let Ok((_, mut sprite)) = query.get_single_mut() else {
         return;
     };
 
@@ -150,26 +396,21 @@ fn on_resize_system(
     };
 
     let new_size = Extent3d {
-        width: window_size.width as u32,
-        height: window_size.height as u32,
+        width: region.width,
+        height: region.height,
         ..default()
     };
     image.resize(new_size);
-}
+    image.sampler = if region.filter_nearest {
+        ImageSampler::nearest()
+    } else {
+        ImageSampler::linear()
+    };
 
-fn on_move_system(
-    mut window_position: ResMut<WindowPosition>,
-    mut move_reader: EventReader<WindowMoved>,
-    position_sender: Res<PositionSender>,
-) {
-    for e in move_reader.read() {
-        window_position.x = e.position.x as u32;
-        window_position.y = e.position.y as u32;
-        position_sender
-            .0
-            .send((window_position.x, window_position.y))
-            .unwrap();
-    }
+    sprite.custom_size = Some(Vec2::new(
+        region.width as f32 * region.zoom_factor,
+        region.height as f32 * region.zoom_factor,
+    ));
 }
 
 fn on_focus_system(
@@ -197,6 +438,8 @@ fn on_focus_system(
 fn update_sprite_image(
     mut images: ResMut<Assets<Image>>,
     frame_receiver: Res<FrameReceiver>,
+    mut last_frame: ResMut<LastFrame>,
+    region: Res<CaptureRegion>,
     window: Single<&Window>,
     query: Query<(&Screencap, &mut Sprite)>,
 ) {
@@ -209,6 +452,9 @@ fn update_sprite_image(
     };
 
     if let Ok(new_frame) = frame_receiver.0.try_recv() {
+        if new_frame.len() == (region.width * region.height * 4) as usize {
+            last_frame.0 = Some((region.width, region.height, new_frame.clone()));
+        }
         if !window.focused {
             image.data = new_frame;
         } else {
@@ -218,6 +464,33 @@ fn update_sprite_image(
     }
 }
 
+fn save_frame_on_keypress(keyboard: Res<ButtonInput<KeyCode>>, last_frame: Res<LastFrame>) {
+    if !keyboard.just_pressed(KeyCode::KeyS) {
+        return;
+    }
+
+    let Some((width, height, data)) = last_frame.0.clone() else {
+        println!("No frame captured yet, nothing to save");
+        return;
+    };
+
+    let Some(buffer) = ImageBuffer::<Rgba<u8>, _>::from_raw(width, height, data) else {
+        println!("Captured frame size didn't match {width}x{height}, not saving");
+        return;
+    };
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let path = format!("capture_{timestamp}.png");
+
+    match buffer.save(&path) {
+        Ok(()) => println!("Saved capture to {path}"),
+        Err(e) => println!("Failed to save capture to {path}: {e}"),
+    }
+}
+
 fn crop_frame(
     frame: &[u8],
     original_width: u32,
@@ -243,3 +516,189 @@ fn crop_frame(
     }
     new_frame
 }
+
+/// Alternative output path for remote/SSH sessions: instead of opening a
+/// Bevy window, print the captured region straight into the terminal, scaled
+/// to the terminal's size. Each character cell encodes two vertically stacked
+/// samples via the upper-half-block glyph (foreground = top, background =
+/// bottom) unless `lores` is set, in which case each cell is one full block
+/// holding a single sample.
+fn run_terminal_preview(frame_receiver: Receiver<Vec<u8>>, region: CaptureRegion, lores: bool) {
+    print!("\x1b[2J");
+
+    loop {
+        let Ok(frame) = frame_receiver.recv() else {
+            break;
+        };
+
+        let (cols, rows) = terminal_size()
+            .map(|(Width(w), Height(h))| (w as u32, h as u32))
+            .unwrap_or((80, 24));
+        if cols == 0 || rows == 0 {
+            continue;
+        }
+
+        let mut out = String::from("\x1b[H");
+        if lores {
+            render_lores(&frame, region.width, region.height, cols, rows, &mut out);
+        } else {
+            render_halfblocks(&frame, region.width, region.height, cols, rows, &mut out);
+        }
+        out.push_str("\x1b[0m");
+
+        print!("{out}");
+        let _ = std::io::stdout().flush();
+    }
+}
+
+fn sample_pixel(frame: &[u8], src_width: u32, src_height: u32, x: u32, y: u32) -> (u8, u8, u8) {
+    let x = x.min(src_width.saturating_sub(1));
+    let y = y.min(src_height.saturating_sub(1));
+    let index = ((y * src_width + x) * 4) as usize;
+    if index + 2 >= frame.len() {
+        return (0, 0, 0);
+    }
+    (frame[index], frame[index + 1], frame[index + 2])
+}
+
+/// One cell per pixel, drawn with a full block glyph, for terminals that
+/// can't afford the half-block trick's vertical resolution.
+fn render_lores(
+    frame: &[u8],
+    src_width: u32,
+    src_height: u32,
+    cols: u32,
+    rows: u32,
+    out: &mut String,
+) {
+    for row in 0..rows {
+        let src_y = row * src_height / rows;
+        for col in 0..cols {
+            let src_x = col * src_width / cols;
+            let (r, g, b) = sample_pixel(frame, src_width, src_height, src_x, src_y);
+            out.push_str(&format!("\x1b[38;2;{r};{g};{b}m\u{2588}"));
+        }
+        out.push_str("\x1b[0m\r\n");
+    }
+}
+
+/// Two source rows per text row: the upper-half-block glyph's foreground
+/// paints the top pixel and its background paints the bottom one.
+fn render_halfblocks(
+    frame: &[u8],
+    src_width: u32,
+    src_height: u32,
+    cols: u32,
+    rows: u32,
+    out: &mut String,
+) {
+    let sample_rows = rows * 2;
+    for row in 0..rows {
+        let top_y = (row * 2) * src_height / sample_rows;
+        let bottom_y = (row * 2 + 1) * src_height / sample_rows;
+        for col in 0..cols {
+            let src_x = col * src_width / cols;
+            let (tr, tg, tb) = sample_pixel(frame, src_width, src_height, src_x, top_y);
+            let (br, bg, bb) = sample_pixel(frame, src_width, src_height, src_x, bottom_y);
+            out.push_str(&format!(
+                "\x1b[38;2;{tr};{tg};{tb}m\x1b[48;2;{br};{bg};{bb}m\u{2580}"
+            ));
+        }
+        out.push_str("\x1b[0m\r\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_shrinks_region_larger_than_monitor() {
+        let monitor = MonitorBounds {
+            x: 0,
+            y: 0,
+            width: 100,
+            height: 50,
+        };
+        let mut region = CaptureRegion {
+            x: 10,
+            y: 10,
+            width: 200,
+            height: 200,
+            zoom_factor: 1.0,
+            filter_nearest: false,
+        };
+
+        clamp_region_to_monitor(&mut region, &monitor);
+
+        assert_eq!(region.width, 100);
+        assert_eq!(region.height, 50);
+        assert_eq!(region.x, 0);
+        assert_eq!(region.y, 0);
+    }
+
+    #[test]
+    fn clamp_handles_zero_size_monitor() {
+        let monitor = MonitorBounds {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+        };
+        let mut region = CaptureRegion {
+            x: 5,
+            y: 5,
+            width: 64,
+            height: 64,
+            zoom_factor: 1.0,
+            filter_nearest: false,
+        };
+
+        clamp_region_to_monitor(&mut region, &monitor);
+
+        assert_eq!(region.width, 1);
+        assert_eq!(region.height, 1);
+        assert_eq!(region.x, 0);
+        assert_eq!(region.y, 0);
+    }
+
+    #[test]
+    fn clamp_leaves_region_already_inside_monitor_untouched() {
+        let monitor = MonitorBounds {
+            x: 0,
+            y: 0,
+            width: 1920,
+            height: 1080,
+        };
+        let mut region = CaptureRegion {
+            x: 10,
+            y: 20,
+            width: 64,
+            height: 64,
+            zoom_factor: 1.0,
+            filter_nearest: false,
+        };
+
+        clamp_region_to_monitor(&mut region, &monitor);
+
+        assert_eq!((region.x, region.y, region.width, region.height), (10, 20, 64, 64));
+    }
+
+    #[test]
+    fn sample_pixel_clamps_out_of_bounds_coordinates() {
+        #[rustfmt::skip]
+        let frame = vec![
+            1, 2, 3, 4,      5, 6, 7, 8,
+            9, 10, 11, 12,   13, 14, 15, 16,
+        ];
+
+        assert_eq!(sample_pixel(&frame, 2, 2, 5, 5), (13, 14, 15));
+    }
+
+    #[test]
+    fn sample_pixel_returns_black_when_frame_is_too_short() {
+        let frame = vec![0u8; 4];
+
+        assert_eq!(sample_pixel(&frame, 2, 2, 1, 1), (0, 0, 0));
+    }
+}
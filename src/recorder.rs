@@ -0,0 +1,199 @@
+//! Offscreen recording: the shared region is additionally rendered into a
+//! `RenderTarget::Image` instead of only the primary window, read back every
+//! frame, and handed to a background thread that writes a numbered PNG
+//! sequence. This lets a recording keep going even while the widget window
+//! is minimized, and keeps the readback off the main schedule so it can't
+//! stall the frame rate the way disk I/O on `Update` would.
+
+use bevy::prelude::*;
+use bevy::render::{
+    Extract, Render, RenderApp, RenderSet,
+    render_asset::RenderAssets,
+    render_graph::{self, RenderGraph, RenderLabel},
+    render_resource::{
+        Buffer, BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Maintain, MapMode,
+        TexelCopyBufferInfo, TexelCopyBufferLayout,
+    },
+    renderer::{RenderContext, RenderDevice},
+    texture::GpuImage,
+};
+use crossbeam_channel::{Receiver, Sender, unbounded};
+use std::path::PathBuf;
+use std::thread;
+
+/// Which render target to read frames back from, and where to write them.
+/// Inserted before `RecorderPlugin` is added; the plugin is only added at
+/// all when `--record` was passed, so nothing here costs anything otherwise.
+#[derive(Resource, Clone)]
+pub struct RecordTarget {
+    pub image: Handle<Image>,
+    pub width: u32,
+    pub height: u32,
+    pub out_dir: PathBuf,
+}
+
+#[derive(Resource, Deref)]
+struct FrameSender(Sender<(u32, u32, Vec<u8>)>);
+
+/// GPU-side staging buffer the offscreen texture gets copied into each
+/// frame so its bytes can be mapped back to the CPU. Created lazily once
+/// `RenderDevice` is available in the render world.
+#[derive(Resource, Default)]
+struct ReadbackBuffer(Option<Buffer>);
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+struct ImageCopyLabel;
+
+fn padded_bytes_per_row(width: u32) -> u32 {
+    const ALIGN: u32 = 256;
+    (width * 4).div_ceil(ALIGN) * ALIGN
+}
+
+pub struct RecorderPlugin;
+
+impl Plugin for RecorderPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(target) = app.world().get_resource::<RecordTarget>().cloned() else {
+            println!("RecorderPlugin added without a RecordTarget resource, not recording");
+            return;
+        };
+
+        let (frame_tx, frame_rx) = unbounded();
+        spawn_png_sequence_writer(target.out_dir.clone(), frame_rx);
+
+        let render_app = app.sub_app_mut(RenderApp);
+        render_app
+            .insert_resource(FrameSender(frame_tx))
+            .insert_resource(ReadbackBuffer::default())
+            .add_systems(ExtractSchedule, extract_record_target)
+            .add_systems(
+                Render,
+                (ensure_readback_buffer, save_copied_images.after(RenderSet::Render)),
+            );
+
+        let mut graph = render_app.world_mut().resource_mut::<RenderGraph>();
+        graph.add_node(ImageCopyLabel, ImageCopyDriverNode);
+        graph.add_node_edge(bevy::render::graph::CameraDriverLabel, ImageCopyLabel);
+    }
+}
+
+fn extract_record_target(mut commands: Commands, target: Extract<Res<RecordTarget>>) {
+    commands.insert_resource(target.clone());
+}
+
+fn ensure_readback_buffer(
+    mut readback: ResMut<ReadbackBuffer>,
+    render_device: Res<RenderDevice>,
+    target: Res<RecordTarget>,
+) {
+    if readback.0.is_some() {
+        return;
+    }
+
+    let size = (padded_bytes_per_row(target.width) * target.height) as u64;
+    readback.0 = Some(render_device.create_buffer(&BufferDescriptor {
+        label: Some("recorder_readback_buffer"),
+        size,
+        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    }));
+}
+
+/// Runs once per frame in the render graph, after the camera has drawn into
+/// `RecordTarget::image`, and copies that texture into the readback buffer.
+struct ImageCopyDriverNode;
+
+impl render_graph::Node for ImageCopyDriverNode {
+    fn run(
+        &self,
+        _graph: &mut render_graph::RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), render_graph::NodeRunError> {
+        let Some(target) = world.get_resource::<RecordTarget>() else {
+            return Ok(());
+        };
+        let Some(buffer) = world
+            .get_resource::<ReadbackBuffer>()
+            .and_then(|b| b.0.as_ref())
+        else {
+            return Ok(());
+        };
+        let Some(gpu_image) = world
+            .resource::<RenderAssets<GpuImage>>()
+            .get(&target.image)
+        else {
+            return Ok(());
+        };
+
+        let mut encoder = render_context
+            .render_device()
+            .create_command_encoder(&CommandEncoderDescriptor::default());
+
+        encoder.copy_texture_to_buffer(
+            gpu_image.texture.as_image_copy(),
+            TexelCopyBufferInfo {
+                buffer,
+                layout: TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row(target.width)),
+                    rows_per_image: None,
+                },
+            },
+            gpu_image.size,
+        );
+
+        render_context.add_command_buffer(encoder.finish());
+        Ok(())
+    }
+}
+
+/// Maps the readback buffer on the render world's own schedule (never the
+/// main one) and forwards the unpadded RGBA bytes to the writer thread.
+fn save_copied_images(
+    readback: Res<ReadbackBuffer>,
+    target: Res<RecordTarget>,
+    render_device: Res<RenderDevice>,
+    frame_sender: Option<Res<FrameSender>>,
+) {
+    let (Some(buffer), Some(frame_sender)) = (readback.0.as_ref(), frame_sender) else {
+        return;
+    };
+
+    let slice = buffer.slice(..);
+    slice.map_async(MapMode::Read, |_| {});
+    render_device.poll(Maintain::Wait);
+
+    let padded = padded_bytes_per_row(target.width) as usize;
+    let unpadded_width = (target.width * 4) as usize;
+
+    let data = slice.get_mapped_range();
+    let mut frame = Vec::with_capacity(unpadded_width * target.height as usize);
+    for row in data.chunks(padded) {
+        frame.extend_from_slice(&row[..unpadded_width]);
+    }
+    drop(data);
+    buffer.unmap();
+
+    if frame_sender.send((target.width, target.height, frame)).is_err() {
+        println!("Recorder writer thread is gone, stopping readback");
+    }
+}
+
+fn spawn_png_sequence_writer(out_dir: PathBuf, receiver: Receiver<(u32, u32, Vec<u8>)>) {
+    thread::spawn(move || {
+        if let Err(e) = std::fs::create_dir_all(&out_dir) {
+            println!("Failed to create recording directory {out_dir:?}: {e}");
+            return;
+        }
+
+        let mut frame_index: u64 = 0;
+        while let Ok((width, height, frame)) = receiver.recv() {
+            let path = out_dir.join(format!("frame_{frame_index:06}.png"));
+            match image::save_buffer(&path, &frame, width, height, image::ColorType::Rgba8) {
+                Ok(()) => frame_index += 1,
+                Err(e) => println!("Failed to write recorded frame {path:?}: {e}"),
+            }
+        }
+    });
+}